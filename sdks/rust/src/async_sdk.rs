@@ -0,0 +1,207 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::sync::Arc;
+
+use futures::Sink;
+use futures03::channel::mpsc;
+use futures03::compat::{Future01CompatExt, Stream01CompatExt};
+use futures03::{Stream, StreamExt};
+use grpcio::{Channel, ChannelBuilder, EnvBuilder, WriteFlags};
+
+use crate::backoff::Backoff;
+use crate::errors::{Error, Result};
+use crate::grpc::sdk::{Duration, Empty, GameServer, KeyValue};
+use crate::grpc::sdk_grpc::SdkClient;
+
+const PORT_ENV_VAR: &str = "AGONES_SDK_GRPC_PORT";
+const DEFAULT_PORT: &str = "9357";
+
+/// `AsyncSdk` is the async/await counterpart to [`crate::Sdk`]. It is built
+/// on the same generated `SdkClient`, but drives every call through its
+/// `_async` variants so a game server running on an async executor does not
+/// need to dedicate an OS thread per SDK interaction.
+pub struct AsyncSdk {
+    client: SdkClient,
+    channel: Channel,
+}
+
+impl AsyncSdk {
+    /// Creates a new AsyncSdk instance, and connects to the SDK server
+    /// sidecar.
+    pub fn new() -> Result<Self> {
+        let addr = format!(
+            "localhost:{}",
+            env::var(PORT_ENV_VAR).unwrap_or_else(|_| DEFAULT_PORT.to_owned())
+        );
+        let env = Arc::new(EnvBuilder::new().build());
+        let channel = ChannelBuilder::new(env).connect(&addr);
+        let client = SdkClient::new(channel.clone());
+
+        Ok(AsyncSdk { client, channel })
+    }
+
+    /// Marks this Game Server as ready to receive connections.
+    pub async fn ready(&self) -> Result<()> {
+        self.client
+            .ready_async(&Empty::default())?
+            .compat()
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
+    /// Marks this Game Server as allocated.
+    pub async fn allocate(&self) -> Result<()> {
+        self.client
+            .allocate_async(&Empty::default())?
+            .compat()
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
+    /// Marks this Game Server for shutdown.
+    pub async fn shutdown(&self) -> Result<()> {
+        self.client
+            .shutdown_async(&Empty::default())?
+            .compat()
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
+    /// Sends a single ping down the Health stream.
+    pub async fn health(&self) -> Result<()> {
+        let (sender, receiver) = self.client.health_opt(grpcio::CallOption::default())?;
+        sender
+            .send((Empty::default(), WriteFlags::default()))
+            .compat()
+            .await
+            .map_err(Error::from)?;
+        drop(receiver);
+        Ok(())
+    }
+
+    /// Retrieves the current GameServer details.
+    pub async fn get_game_server(&self) -> Result<GameServer> {
+        self.client
+            .get_game_server_async(&Empty::default())?
+            .compat()
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Watches for changes to the backing GameServer configuration, returning
+    /// a `Stream` of updates rather than taking a blocking callback.
+    pub fn watch_gameserver(&self) -> Result<impl Stream<Item = Result<GameServer>>> {
+        let receiver = self
+            .client
+            .watch_game_server(&Empty::default())
+            .map_err(Error::from)?;
+        Ok(receiver.compat().map(|item| item.map_err(Error::from)))
+    }
+
+    /// Watches for changes to the backing GameServer configuration like
+    /// [`watch_gameserver`](AsyncSdk::watch_gameserver), but reconnects the
+    /// underlying stream on `backoff` whenever it errors out or ends,
+    /// re-emitting the latest GameServer snapshot immediately on reconnect so
+    /// the returned `Stream` never goes silent across sidecar churn. Spawns a
+    /// background task on the current executor to drive the reconnect loop.
+    pub fn watch_gameserver_resilient(&self, backoff: Backoff) -> impl Stream<Item = Result<GameServer>> {
+        let channel = self.channel.clone();
+        let (tx, rx) = mpsc::unbounded();
+
+        tokio::spawn(async move {
+            let client = SdkClient::new(channel);
+            let mut attempt = 0u32;
+            loop {
+                if let Ok(receiver) = client.get_game_server_async(&Empty::default()) {
+                    if let Ok(gameserver) = receiver.compat().await {
+                        if tx.unbounded_send(Ok(gameserver)).is_err() {
+                            return;
+                        }
+                    }
+                }
+
+                if let Ok(stream) = client.watch_game_server(&Empty::default()) {
+                    let mut stream = stream.compat();
+                    while let Some(item) = stream.next().await {
+                        match item {
+                            Ok(gameserver) => {
+                                // Only reset the backoff once an item has actually
+                                // been pulled off the stream: grpcio can return
+                                // `Ok` from `watch_game_server` even when the
+                                // sidecar is unreachable, surfacing the error only
+                                // on the first poll.
+                                attempt = 0;
+                                if tx.unbounded_send(Ok(gameserver)).is_err() {
+                                    return;
+                                }
+                            }
+                            Err(_) => break,
+                        }
+                    }
+                }
+
+                tokio::time::delay_for(backoff.delay_for_attempt(attempt)).await;
+                attempt = attempt.saturating_add(1);
+            }
+        });
+
+        rx
+    }
+
+    /// Applies a Label with the given key and value to the backing GameServer
+    /// metadata.
+    pub async fn set_label(&self, key: &str, value: &str) -> Result<()> {
+        let mut kv = KeyValue::default();
+        kv.key = key.to_owned();
+        kv.value = value.to_owned();
+        self.client
+            .set_label_async(&kv)?
+            .compat()
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
+    /// Applies an Annotation with the given key and value to the backing
+    /// GameServer metadata.
+    pub async fn set_annotation(&self, key: &str, value: &str) -> Result<()> {
+        let mut kv = KeyValue::default();
+        kv.key = key.to_owned();
+        kv.value = value.to_owned();
+        self.client
+            .set_annotation_async(&kv)?
+            .compat()
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
+    /// Marks the GameServer as Reserved for the given duration, after which
+    /// it will return automatically to a Ready state.
+    pub async fn reserve(&self, duration: std::time::Duration) -> Result<()> {
+        let mut d = Duration::default();
+        d.seconds = duration.as_secs() as i64;
+        self.client
+            .reserve_async(&d)?
+            .compat()
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+}
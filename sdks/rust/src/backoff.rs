@@ -0,0 +1,71 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+/// A capped exponential backoff, used to pace stream-reconnection attempts
+/// (e.g. `watch_gameserver_resilient`) so a flapping sidecar isn't hammered
+/// with reconnects.
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    /// Creates a new `Backoff` starting at `initial` and doubling on each
+    /// failed attempt, up to `max`.
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Backoff { initial, max }
+    }
+
+    /// The delay to wait before the `attempt`'th reconnect (0-indexed).
+    pub(crate) fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.initial.checked_mul(factor).unwrap_or(self.max).min(self.max)
+    }
+}
+
+impl Default for Backoff {
+    /// Starts at 500ms, doubling up to a cap of 30 seconds.
+    fn default() -> Self {
+        Backoff::new(Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_each_attempt() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(30));
+        assert_eq!(backoff.delay_for_attempt(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay_for_attempt(1), Duration::from_millis(200));
+        assert_eq!(backoff.delay_for_attempt(2), Duration::from_millis(400));
+        assert_eq!(backoff.delay_for_attempt(3), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn caps_at_max() {
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(10));
+        assert_eq!(backoff.delay_for_attempt(10), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn does_not_overflow_on_large_attempt_counts() {
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(30));
+        assert_eq!(backoff.delay_for_attempt(u32::MAX), Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+/// Errors that can occur while talking to the Agones sidecar.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying gRPC call failed.
+    Grpc(grpcio::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Grpc(e) => write!(f, "gRPC error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<grpcio::Error> for Error {
+    fn from(e: grpcio::Error) -> Self {
+        Error::Grpc(e)
+    }
+}
+
+/// A `Result` alias where the `Err` case is an Agones SDK [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
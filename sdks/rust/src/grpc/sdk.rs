@@ -0,0 +1,95 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// This code was autogenerated. Do not edit directly.
+// This file is generated. Do not edit
+// @generated
+
+#![allow(unknown_lints)]
+#![allow(clippy)]
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+
+use std::collections::HashMap;
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Empty {}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Duration {
+    pub seconds: i64,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct ObjectMeta {
+    pub name: String,
+    pub namespace: String,
+    pub uid: String,
+    pub resource_version: String,
+    pub generation: i64,
+    pub creation_timestamp: i64,
+    pub deletion_timestamp: i64,
+    pub annotations: HashMap<String, String>,
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct GameServerStatusPort {
+    pub name: String,
+    pub port: i32,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct GameServerStatus {
+    pub state: String,
+    pub address: String,
+    pub ports: Vec<GameServerStatusPort>,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct GameServerSpecHealth {
+    pub disabled: bool,
+    pub period_seconds: i32,
+    pub failure_threshold: i32,
+    pub initial_delay_seconds: i32,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct GameServerSpec {
+    pub health: Option<GameServerSpecHealth>,
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct GameServer {
+    pub object_meta: Option<ObjectMeta>,
+    pub spec: Option<GameServerSpec>,
+    pub status: Option<GameServerStatus>,
+}
@@ -0,0 +1,141 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::Sink;
+use futures03::compat::Future01CompatExt;
+use grpcio::{CallOption, Channel, WriteFlags};
+use tokio::sync::oneshot;
+
+use crate::grpc::sdk::Empty;
+use crate::grpc::sdk_grpc::SdkClient;
+
+/// A handle to the background health-ping task started by
+/// [`crate::Sdk::start_health_task`]. Dropping the handle also stops the
+/// task, without waiting for it to exit; [`stop`](HealthTaskHandle::stop)
+/// stops it and waits. Keep the handle alive for as long as pings should
+/// continue.
+pub struct HealthTaskHandle {
+    stop: Option<oneshot::Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+    last_ping_unix_secs: Arc<AtomicI64>,
+}
+
+impl HealthTaskHandle {
+    /// The unix timestamp, in seconds, of the last Health ping that was
+    /// successfully sent to the sidecar, or `0` if none has succeeded yet.
+    pub fn last_ping_unix_secs(&self) -> i64 {
+        self.last_ping_unix_secs.load(Ordering::Relaxed)
+    }
+
+    /// Signals the health task to stop and blocks until it has exited.
+    pub fn stop(mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+/// Starts the reconnecting health-ping task on a dedicated thread, driving
+/// its cadence off a single async timer rather than `thread::sleep`, and
+/// returns a handle to it.
+pub(crate) fn start(channel: Channel, interval: Duration) -> HealthTaskHandle {
+    let last_ping_unix_secs = Arc::new(AtomicI64::new(0));
+    let (stop_tx, stop_rx) = oneshot::channel();
+
+    let task_last_ping = last_ping_unix_secs.clone();
+    let join = thread::Builder::new()
+        .name("agones-health".to_owned())
+        .spawn(move || {
+            let mut runtime = tokio::runtime::Runtime::new()
+                .expect("failed to start agones-health runtime");
+            runtime.block_on(run(channel, interval, task_last_ping, stop_rx));
+        })
+        .expect("failed to spawn agones-health thread");
+
+    HealthTaskHandle {
+        stop: Some(stop_tx),
+        join: Some(join),
+        last_ping_unix_secs,
+    }
+}
+
+async fn run(
+    channel: Channel,
+    interval: Duration,
+    last_ping_unix_secs: Arc<AtomicI64>,
+    mut stop_rx: oneshot::Receiver<()>,
+) {
+    let client = SdkClient::new(channel);
+    // Missed ticks are coalesced rather than queued as a backlog: once the
+    // schedule has fallen behind, the next tick is pushed to one `interval`
+    // from now, so a slow sidecar just delays the next ping instead of
+    // flooding it.
+    let mut next_tick = tokio::time::Instant::now();
+    let mut sender = open_stream(&client);
+
+    loop {
+        tokio::select! {
+            _ = &mut stop_rx => return,
+            _ = tokio::time::delay_until(next_tick) => {
+                let now = tokio::time::Instant::now();
+                next_tick = next_tick + interval;
+                if next_tick <= now {
+                    next_tick = now + interval;
+                }
+                let current = match sender.take() {
+                    Some(s) => s,
+                    None => match open_stream(&client) {
+                        Some(s) => s,
+                        None => continue,
+                    },
+                };
+                match current.send((Empty::default(), WriteFlags::default())).compat().await {
+                    Ok(s) => {
+                        last_ping_unix_secs.store(now_unix_secs(), Ordering::Relaxed);
+                        sender = Some(s);
+                    }
+                    Err(_) => {
+                        // The stream errored out; drop it and reconnect on the next tick.
+                        sender = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn open_stream(client: &SdkClient) -> Option<grpcio::ClientCStreamSender<Empty>> {
+    match client.health_opt(CallOption::default()) {
+        Ok((sender, receiver)) => {
+            drop(receiver);
+            Some(sender)
+        }
+        Err(_) => None,
+    }
+}
+
+fn now_unix_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,103 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+
+use futures::sync::mpsc::{self, UnboundedSender};
+use futures::{Future, Sink, Stream};
+use grpcio::{RpcContext, ServerStreamingSink, UnarySink, WriteFlags};
+
+use crate::grpc::health::{HealthCheckRequest, HealthCheckResponse, ServingStatus};
+use crate::grpc::health_grpc::Health;
+
+struct Inner {
+    status: ServingStatus,
+    subscribers: Vec<UnboundedSender<HealthCheckResponse>>,
+}
+
+/// An implementation of the standard `grpc.health.v1.Health` service (unary
+/// `Check` and server-streaming `Watch`) whose status is driven by a game
+/// server's [`crate::Sdk`] lifecycle: `Serving` once `ready()` succeeds,
+/// `NotServing` after `shutdown()`. Register it alongside the SDK's own
+/// service with [`crate::create_health`], the same way `create_sdk` wires up
+/// `Sdk`'s handlers:
+///
+/// ```ignore
+/// let health_service = sdk.health_service();
+/// server_builder.register_service(create_health(health_service));
+/// ```
+#[derive(Clone)]
+pub struct HealthService {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl HealthService {
+    /// Creates a new `HealthService`, initially reporting `Unknown` until the
+    /// backing `Sdk` marks itself ready.
+    pub(crate) fn new() -> Self {
+        HealthService {
+            inner: Arc::new(Mutex::new(Inner {
+                status: ServingStatus::Unknown,
+                subscribers: Vec::new(),
+            })),
+        }
+    }
+
+    pub(crate) fn set_serving(&self) {
+        self.set_status(ServingStatus::Serving);
+    }
+
+    pub(crate) fn set_not_serving(&self) {
+        self.set_status(ServingStatus::NotServing);
+    }
+
+    /// Updates the current status and pushes it to every open `Watch` stream,
+    /// dropping any subscriber whose stream has since gone away.
+    fn set_status(&self, status: ServingStatus) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.status = status;
+        let mut resp = HealthCheckResponse::default();
+        resp.status = status;
+        inner
+            .subscribers
+            .retain(|tx| tx.unbounded_send(resp.clone()).is_ok());
+    }
+
+    fn current_status(&self) -> ServingStatus {
+        self.inner.lock().unwrap().status
+    }
+}
+
+impl Health for HealthService {
+    fn check(&self, ctx: RpcContext, _req: HealthCheckRequest, sink: UnarySink<HealthCheckResponse>) {
+        let mut resp = HealthCheckResponse::default();
+        resp.status = self.current_status();
+        ctx.spawn(sink.success(resp).map_err(|_| ()));
+    }
+
+    fn watch(&self, ctx: RpcContext, _req: HealthCheckRequest, sink: ServerStreamingSink<HealthCheckResponse>) {
+        let mut initial = HealthCheckResponse::default();
+        initial.status = self.current_status();
+
+        let (tx, rx) = mpsc::unbounded();
+        self.inner.lock().unwrap().subscribers.push(tx);
+
+        // Emit the current status immediately, then every subsequent
+        // transition pushed by `set_serving`/`set_not_serving` for as long as
+        // the caller keeps the stream open.
+        let updates = Stream::chain(futures::stream::once(Ok(initial)), rx.map_err(|_| unreachable!()))
+            .map(|resp| (resp, WriteFlags::default()));
+        ctx.spawn(sink.send_all(updates).map(|_| ()).map_err(|_| ()));
+    }
+}
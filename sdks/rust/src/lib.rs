@@ -0,0 +1,36 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod async_sdk;
+mod backoff;
+mod errors;
+mod grpc;
+mod health;
+mod health_service;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod sdk;
+mod watch;
+
+pub use crate::async_sdk::AsyncSdk;
+pub use crate::backoff::Backoff;
+pub use crate::errors::{Error, Result};
+pub use crate::grpc::health::{HealthCheckRequest, HealthCheckResponse, ServingStatus};
+pub use crate::grpc::health_grpc::create_health;
+pub use crate::health::HealthTaskHandle;
+pub use crate::health_service::HealthService;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::Metrics;
+pub use crate::sdk::Sdk;
+pub use crate::watch::WatchTaskHandle;
@@ -0,0 +1,194 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus instrumentation for SDK calls and GameServer state transitions,
+//! gated behind the `metrics` feature.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, ToSocketAddrs};
+use std::thread;
+use std::time::Instant;
+
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+
+use crate::errors::Error;
+
+/// `Metrics` holds the Prometheus registry and collectors used to
+/// instrument `Sdk` calls (see [`crate::Sdk::with_metrics`]) and observed
+/// GameServer state transitions. `AsyncSdk` calls are not instrumented.
+pub struct Metrics {
+    registry: Registry,
+    calls_total: IntCounterVec,
+    call_errors_total: IntCounterVec,
+    call_duration_seconds: HistogramVec,
+    gameserver_state_total: IntCounterVec,
+}
+
+impl Metrics {
+    /// Builds a new `Metrics` instance with its own registry.
+    pub fn new() -> prometheus::Result<Self> {
+        let calls_total = IntCounterVec::new(
+            Opts::new("agones_sdk_calls_total", "Total number of Agones SDK calls, by method."),
+            &["method"],
+        )?;
+        let call_errors_total = IntCounterVec::new(
+            Opts::new(
+                "agones_sdk_call_errors_total",
+                "Total number of failed Agones SDK calls, by method and error.",
+            ),
+            &["method", "error"],
+        )?;
+        let call_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "agones_sdk_call_duration_seconds",
+                "Agones SDK call latency in seconds, by method.",
+            ),
+            &["method"],
+        )?;
+        let gameserver_state_total = IntCounterVec::new(
+            Opts::new(
+                "agones_gameserver_state_total",
+                "Total number of GameServer state transitions observed via watch_gameserver, by state.",
+            ),
+            &["state"],
+        )?;
+
+        let registry = Registry::new();
+        registry.register(Box::new(calls_total.clone()))?;
+        registry.register(Box::new(call_errors_total.clone()))?;
+        registry.register(Box::new(call_duration_seconds.clone()))?;
+        registry.register(Box::new(gameserver_state_total.clone()))?;
+
+        Ok(Metrics {
+            registry,
+            calls_total,
+            call_errors_total,
+            call_duration_seconds,
+            gameserver_state_total,
+        })
+    }
+
+    /// Runs `f`, recording its call count, latency and (on failure) error
+    /// counters under `method`.
+    pub(crate) fn observe_call<T>(&self, method: &str, f: impl FnOnce() -> crate::errors::Result<T>) -> crate::errors::Result<T> {
+        let start = Instant::now();
+        let result = f();
+        self.calls_total.with_label_values(&[method]).inc();
+        self.call_duration_seconds
+            .with_label_values(&[method])
+            .observe(start.elapsed().as_secs_f64());
+        if let Err(ref e) = result {
+            self.call_errors_total
+                .with_label_values(&[method, &error_label(e)])
+                .inc();
+        }
+        result
+    }
+
+    /// Records that `watch_gameserver` observed the GameServer transition
+    /// into `state`.
+    pub fn observe_gameserver_state(&self, state: &str) {
+        self.gameserver_state_total.with_label_values(&[state]).inc();
+    }
+
+    /// Renders all registered metrics in the Prometheus text exposition
+    /// format.
+    pub fn gather_text(&self) -> String {
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&metric_families, &mut buffer)
+            .expect("metric families should always encode");
+        String::from_utf8(buffer).expect("prometheus text output is valid utf8")
+    }
+
+    /// Spawns a minimal background HTTP listener on `addr` that serves the
+    /// current metrics as `GET /metrics`, so the game server's own metrics
+    /// can be scraped alongside the sidecar's.
+    pub fn serve(self: std::sync::Arc<Self>, addr: impl ToSocketAddrs) -> std::io::Result<thread::JoinHandle<()>> {
+        let listener = TcpListener::bind(addr)?;
+        Ok(thread::Builder::new()
+            .name("agones-metrics".to_owned())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let metrics = self.clone();
+                    if let Ok(mut stream) = stream {
+                        // Best-effort: a scrape that fails to parse or write is simply dropped.
+                        let mut buf = [0u8; 1024];
+                        let _ = stream.read(&mut buf);
+                        let body = metrics.gather_text();
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        let _ = stream.write_all(response.as_bytes());
+                    }
+                }
+            })
+            .expect("failed to spawn agones-metrics thread"))
+    }
+}
+
+/// Maps an `Error` to a bounded-cardinality label for `call_errors_total`.
+/// Uses the gRPC status code rather than the error's `Display` text, which
+/// can embed arbitrary server-supplied messages and would otherwise blow up
+/// a long-running fleet's metrics memory with unbounded label values.
+fn error_label(e: &Error) -> String {
+    match e {
+        Error::Grpc(grpcio::Error::RpcFailure(status)) => format!("{:?}", status.status),
+        Error::Grpc(grpcio::Error::RpcFinished(Some(status))) => format!("{:?}", status.status),
+        Error::Grpc(grpcio::Error::RpcFinished(None)) => "cancelled".to_owned(),
+        Error::Grpc(_) => "transport".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use grpcio::{RpcStatus, RpcStatusCode};
+
+    use super::*;
+
+    #[test]
+    fn maps_rpc_failure_to_its_status_code() {
+        let status = RpcStatus::new(RpcStatusCode::UNAVAILABLE, Some("sidecar down".to_owned()));
+        let err = Error::from(grpcio::Error::RpcFailure(status));
+        assert_eq!(error_label(&err), format!("{:?}", RpcStatusCode::UNAVAILABLE));
+    }
+
+    #[test]
+    fn maps_rpc_finished_with_status_to_its_status_code() {
+        let status = RpcStatus::new(RpcStatusCode::INTERNAL, None);
+        let err = Error::from(grpcio::Error::RpcFinished(Some(status)));
+        assert_eq!(error_label(&err), format!("{:?}", RpcStatusCode::INTERNAL));
+    }
+
+    #[test]
+    fn maps_rpc_finished_without_status_to_cancelled() {
+        let err = Error::from(grpcio::Error::RpcFinished(None));
+        assert_eq!(error_label(&err), "cancelled");
+    }
+
+    #[test]
+    fn maps_other_grpc_errors_to_a_bounded_transport_label() {
+        let err = Error::from(grpcio::Error::RemoteStopped);
+        assert_eq!(error_label(&err), "transport");
+
+        // A server-supplied detail string must never leak into the label: it
+        // would otherwise be unbounded-cardinality data feeding a metrics vec.
+        let status = RpcStatus::new(RpcStatusCode::UNKNOWN, Some("arbitrary server message".to_owned()));
+        let err = Error::from(grpcio::Error::RpcFailure(status));
+        assert!(!error_label(&err).contains("arbitrary server message"));
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use futures::{Future, Sink, Stream};
+use grpcio::{Channel, ChannelBuilder, EnvBuilder, WriteFlags};
+
+use crate::backoff::Backoff;
+use crate::errors::{Error, Result};
+use crate::grpc::sdk::{Duration, Empty, GameServer, KeyValue};
+use crate::grpc::sdk_grpc::SdkClient;
+use crate::health::{self, HealthTaskHandle};
+use crate::health_service::HealthService;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::watch::{self, WatchTaskHandle};
+
+const PORT_ENV_VAR: &str = "AGONES_SDK_GRPC_PORT";
+const DEFAULT_PORT: &str = "9357";
+
+/// `Sdk` is the synchronous Agones SDK client. It talks to the SDK server
+/// sidecar over gRPC on `localhost`, on the port given by the
+/// `AGONES_SDK_GRPC_PORT` environment variable (defaulting to 9357).
+pub struct Sdk {
+    client: SdkClient,
+    channel: Channel,
+    health_service: HealthService,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl Sdk {
+    /// Creates a new Sdk instance, and connects to the SDK server sidecar.
+    pub fn new() -> Result<Self> {
+        let addr = format!(
+            "localhost:{}",
+            env::var(PORT_ENV_VAR).unwrap_or_else(|_| DEFAULT_PORT.to_owned())
+        );
+        let env = Arc::new(EnvBuilder::new().build());
+        let channel = ChannelBuilder::new(env).connect(&addr);
+        let client = SdkClient::new(channel.clone());
+
+        Ok(Sdk {
+            client,
+            channel,
+            health_service: HealthService::new(),
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        })
+    }
+
+    /// Returns a handle to this Sdk's standard `grpc.health.v1.Health`
+    /// service implementation. Register it on the game server's own gRPC
+    /// server with [`crate::grpc::health_grpc::create_health`] so external
+    /// probes can query readiness over the well-known protocol; its status
+    /// tracks this `Sdk`'s `ready()`/`shutdown()` calls.
+    pub fn health_service(&self) -> HealthService {
+        self.health_service.clone()
+    }
+
+    /// Instruments every subsequent `ready`, `allocate`, `shutdown`,
+    /// `get_gameserver`, `set_label`, `set_annotation` and `reserve` call on
+    /// this `Sdk` with `metrics`, recording call counts, latencies and errors,
+    /// and counts the GameServer states seen by `watch_gameserver`. `health`,
+    /// the health task and `watch_gameserver_resilient` are not instrumented,
+    /// and `watch_gameserver` itself records no call count or latency.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    #[cfg(feature = "metrics")]
+    fn call<T>(&self, method: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        match &self.metrics {
+            Some(metrics) => metrics.observe_call(method, f),
+            None => f(),
+        }
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    fn call<T>(&self, _method: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        f()
+    }
+
+    /// Starts a background task that sends a Health ping on `interval`,
+    /// transparently reconnecting the underlying stream if a ping fails.
+    /// Drop or call [`HealthTaskHandle::stop`] on the returned handle to stop
+    /// pinging.
+    pub fn start_health_task(&self, interval: StdDuration) -> HealthTaskHandle {
+        health::start(self.channel.clone(), interval)
+    }
+
+    /// Marks this Game Server as ready to receive connections.
+    pub fn ready(&self) -> Result<()> {
+        let result = self.call("Ready", || {
+            self.client.ready(&Empty::default()).map(|_| ()).map_err(Error::from)
+        });
+        if result.is_ok() {
+            self.health_service.set_serving();
+        }
+        result
+    }
+
+    /// Marks this Game Server as allocated.
+    pub fn allocate(&self) -> Result<()> {
+        self.call("Allocate", || {
+            self.client.allocate(&Empty::default()).map(|_| ()).map_err(Error::from)
+        })
+    }
+
+    /// Marks this Game Server for shutdown.
+    pub fn shutdown(&self) -> Result<()> {
+        let result = self.call("Shutdown", || {
+            self.client.shutdown(&Empty::default()).map(|_| ()).map_err(Error::from)
+        });
+        if result.is_ok() {
+            self.health_service.set_not_serving();
+        }
+        result
+    }
+
+    /// Sends a single ping down the Health stream. Each call opens a new
+    /// Health stream, sends one ping on it and closes it. Consumes and returns
+    /// `self`, e.g.
+    ///
+    /// ```ignore
+    /// let mut sdk = sdk;
+    /// match sdk.health() {
+    ///     (s, Ok(_)) => sdk = s,
+    ///     (s, Err(e)) => sdk = s,
+    /// }
+    /// ```
+    pub fn health(self) -> (Self, Result<()>) {
+        match self.client.health() {
+            Ok((sender, receiver)) => {
+                let result = sender
+                    .send((Empty::default(), WriteFlags::default()))
+                    .wait()
+                    .map(|_| ())
+                    .map_err(Error::from);
+                // The stream is one-shot per Health(), so drop it immediately;
+                // grpcio will tear the underlying RPC down once `receiver` is dropped.
+                drop(receiver);
+                (self, result)
+            }
+            Err(e) => (self, Err(Error::from(e))),
+        }
+    }
+
+    /// Retrieves the current GameServer details.
+    pub fn get_gameserver(&self) -> Result<GameServer> {
+        self.call("GetGameServer", || {
+            self.client.get_game_server(&Empty::default()).map_err(Error::from)
+        })
+    }
+
+    /// Watches for changes to the backing GameServer configuration, calling
+    /// `callback` with the new value each time the stream yields an update.
+    /// Blocks for as long as the underlying stream stays open.
+    pub fn watch_gameserver<F>(&self, mut callback: F) -> Result<()>
+    where
+        F: FnMut(GameServer),
+    {
+        let stream = self
+            .client
+            .watch_game_server(&Empty::default())
+            .map_err(Error::from)?;
+        #[cfg(feature = "metrics")]
+        let mut last_state: Option<String> = None;
+        for result in stream.wait() {
+            let gameserver = result.map_err(Error::from)?;
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &self.metrics {
+                if let Some(status) = &gameserver.status {
+                    if last_state.as_deref() != Some(status.state.as_str()) {
+                        metrics.observe_gameserver_state(&status.state);
+                        last_state = Some(status.state.clone());
+                    }
+                }
+            }
+            callback(gameserver);
+        }
+        Ok(())
+    }
+
+    /// Watches for changes to the backing GameServer configuration like
+    /// [`watch_gameserver`](Sdk::watch_gameserver), but never gives up: if
+    /// the stream errors out or ends, it is re-opened after `backoff`
+    /// (doubling on every consecutive failure, up to its cap), and the
+    /// latest GameServer snapshot is re-emitted immediately on reconnect so
+    /// `callback` never misses an update that happened while the stream was
+    /// down. Runs on its own background thread; drop or call
+    /// [`WatchTaskHandle::stop`] on the returned handle to stop watching,
+    /// symmetrical with [`start_health_task`](Sdk::start_health_task).
+    pub fn watch_gameserver_resilient<F>(&self, backoff: Backoff, callback: F) -> WatchTaskHandle
+    where
+        F: FnMut(GameServer) + Send + 'static,
+    {
+        watch::start(SdkClient::new(self.channel.clone()), backoff, callback)
+    }
+
+    /// Applies a Label with the given key and value to the backing GameServer
+    /// metadata.
+    pub fn set_label(&self, key: &str, value: &str) -> Result<()> {
+        self.call("SetLabel", || {
+            let mut kv = KeyValue::default();
+            kv.key = key.to_owned();
+            kv.value = value.to_owned();
+            self.client.set_label(&kv).map(|_| ()).map_err(Error::from)
+        })
+    }
+
+    /// Applies an Annotation with the given key and value to the backing
+    /// GameServer metadata.
+    pub fn set_annotation(&self, key: &str, value: &str) -> Result<()> {
+        self.call("SetAnnotation", || {
+            let mut kv = KeyValue::default();
+            kv.key = key.to_owned();
+            kv.value = value.to_owned();
+            self.client.set_annotation(&kv).map(|_| ()).map_err(Error::from)
+        })
+    }
+
+    /// Marks the GameServer as Reserved for the given duration, after which it
+    /// will return automatically to a Ready state.
+    pub fn reserve(&self, duration: std::time::Duration) -> Result<()> {
+        self.call("Reserve", || {
+            let mut d = Duration::default();
+            d.seconds = duration.as_secs() as i64;
+            self.client.reserve(&d).map(|_| ()).map_err(Error::from)
+        })
+    }
+}
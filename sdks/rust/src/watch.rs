@@ -0,0 +1,102 @@
+// Copyright 2019 Google LLC All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use futures::Stream;
+
+use crate::backoff::Backoff;
+use crate::grpc::sdk::{Empty, GameServer};
+use crate::grpc::sdk_grpc::SdkClient;
+
+/// A handle to the background reconnecting watch task started by
+/// [`crate::Sdk::watch_gameserver_resilient`], symmetrical with
+/// [`crate::HealthTaskHandle`].
+pub struct WatchTaskHandle {
+    stop: Arc<AtomicBool>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl WatchTaskHandle {
+    /// Signals the watch task to stop and blocks until it has exited. The
+    /// task only checks for the stop signal between stream items and before
+    /// and after each backoff sleep, so a call blocked waiting on the sidecar,
+    /// or a backoff sleep in progress, runs to its end before the task stops.
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+impl Drop for WatchTaskHandle {
+    /// Signals the watch task to stop without waiting for it to exit.
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+pub(crate) fn start<F>(client: SdkClient, backoff: Backoff, mut callback: F) -> WatchTaskHandle
+where
+    F: FnMut(GameServer) + Send + 'static,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    let task_stop = stop.clone();
+
+    let join = thread::Builder::new()
+        .name("agones-watch".to_owned())
+        .spawn(move || {
+            let mut attempt = 0u32;
+            while !task_stop.load(Ordering::SeqCst) {
+                if let Ok(gameserver) = client.get_game_server(&Empty::default()) {
+                    callback(gameserver);
+                }
+
+                if let Ok(stream) = client.watch_game_server(&Empty::default()) {
+                    for result in stream.wait() {
+                        if task_stop.load(Ordering::SeqCst) {
+                            return;
+                        }
+                        match result {
+                            Ok(gameserver) => {
+                                // Only reset the backoff once an item has
+                                // actually been pulled off the stream: grpcio
+                                // can return `Ok` from `watch_game_server` even
+                                // when the sidecar is unreachable, surfacing
+                                // the error only on the first poll.
+                                attempt = 0;
+                                callback(gameserver);
+                            }
+                            Err(_) => break,
+                        }
+                    }
+                }
+
+                if task_stop.load(Ordering::SeqCst) {
+                    return;
+                }
+                thread::sleep(backoff.delay_for_attempt(attempt));
+                attempt = attempt.saturating_add(1);
+            }
+        })
+        .expect("failed to spawn agones-watch thread");
+
+    WatchTaskHandle {
+        stop,
+        join: Some(join),
+    }
+}